@@ -10,6 +10,7 @@
 
 use c_str::CString;
 use cast;
+use cmp;
 use comm::{Chan, Port};
 use libc::c_int;
 use libc;
@@ -80,6 +81,33 @@ pub enum CloseBehavior {
     CloseAsynchronously,
 }
 
+/// The kind of change reported by an `RtioFsWatcher`.
+pub enum FsEventKind {
+    /// A new file or directory was created at the watched path.
+    FsEventCreate,
+    /// The contents or metadata of the watched path were modified.
+    FsEventModify,
+    /// The watched path was removed.
+    FsEventRemove,
+    /// The watched path was renamed.
+    FsEventRename,
+}
+
+/// A single filesystem change notification delivered by an
+/// `RtioFsWatcher`.
+pub struct FsEvent {
+    /// The path affected by this event. For `FsEventRename` this is the
+    /// path the entry was renamed to.
+    path: Path,
+    /// For `FsEventRename`, the path the entry was renamed from, so old
+    /// and new names can be correlated the way inotify pairs
+    /// `IN_MOVED_FROM`/`IN_MOVED_TO` by cookie. `None` for every other
+    /// event kind.
+    old_path: Option<Path>,
+    /// What kind of change occurred.
+    kind: FsEventKind,
+}
+
 pub struct LocalIo<'a> {
     priv factory: &'a mut IoFactory,
 }
@@ -151,6 +179,11 @@ pub trait IoFactory {
     fn unix_connect(&mut self, path: &CString) -> Result<~RtioPipe, IoError>;
     fn get_host_addresses(&mut self, host: Option<&str>, servname: Option<&str>,
                           hint: Option<ai::Hint>) -> Result<~[ai::Info], IoError>;
+    // FIXME: like get_host_addresses above, the actual getnameinfo(3)-style
+    // resolution runs on the event loop in the native and libuv
+    // implementors; neither lives in this file, so there's no default body
+    // to give this one here either.
+    fn get_address_name(&mut self, addr: SocketAddr) -> Result<~str, IoError>;
 
     // filesystem operations
     fn fs_from_raw_fd(&mut self, fd: c_int, close: CloseBehavior) -> ~RtioFileStream;
@@ -184,6 +217,8 @@ pub trait IoFactory {
             -> Result<~RtioTTY, IoError>;
     fn signal(&mut self, signal: Signum, channel: Chan<Signum>)
         -> Result<~RtioSignal, IoError>;
+    fn fs_watch(&mut self, path: &CString, recursive: bool,
+                channel: Chan<FsEvent>) -> Result<~RtioFsWatcher, IoError>;
 }
 
 pub trait RtioTcpListener : RtioSocket {
@@ -205,6 +240,66 @@ pub trait RtioTcpStream : RtioSocket {
     fn keepalive(&mut self, delay_in_seconds: uint) -> Result<(), IoError>;
     fn letdie(&mut self) -> Result<(), IoError>;
     fn clone(&self) -> ~RtioTcpStream;
+
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> Result<uint, IoError> {
+        let mut total = 0u;
+        for buf in bufs.mut_iter() {
+            match self.read(*buf) {
+                Ok(n) => { total += n; if n < buf.len() { break } }
+                Err(e) => return if total > 0 { Ok(total) } else { Err(e) },
+            }
+        }
+        Ok(total)
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> Result<(), IoError> {
+        for buf in bufs.iter() {
+            try!(self.write(*buf));
+        }
+        Ok(())
+    }
+
+    // FIXME: backed by sendfile(2)/TransmitFile in the native and libuv
+    // implementors; no such backend lives in this file, so the default
+    // always takes the read/write-through-a-buffer fallback below.
+    fn send_file(&mut self, file: &mut RtioFileStream, offset: u64,
+                 len: u64) -> Result<u64, IoError> {
+        let mut buf = [0u8, ..4096];
+        let mut pos = offset;
+        let mut sent = 0u64;
+        while sent < len {
+            let want = cmp::min(buf.len() as u64, len - sent) as uint;
+            let n = try!(file.pread(buf.mut_slice_to(want), pos));
+            if n == 0 { break }
+            try!(self.write(buf.slice_to(n as uint)));
+            pos += n as u64;
+            sent += n as u64;
+        }
+        Ok(sent)
+    }
+
+    // FIXME: these reject with `IoUnavailable` until a backend actually
+    // arms a timer against the operation and cancels it on completion, the
+    // same placeholder `LocalIo::maybe_raise` uses when there's no I/O
+    // services to ask. `None` is a no-op `Ok(())` since there's nothing to
+    // clear when no backend has ever armed a deadline.
+    fn set_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_read_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_write_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
 }
 
 pub trait RtioSocket {
@@ -228,6 +323,25 @@ pub trait RtioUdpSocket : RtioSocket {
     fn ignore_broadcasts(&mut self) -> Result<(), IoError>;
 
     fn clone(&self) -> ~RtioUdpSocket;
+
+    fn set_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_read_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_write_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
 }
 
 pub trait RtioTimer {
@@ -246,6 +360,46 @@ pub trait RtioFileStream {
     fn fsync(&mut self) -> Result<(), IoError>;
     fn datasync(&mut self) -> Result<(), IoError>;
     fn truncate(&mut self, offset: i64) -> Result<(), IoError>;
+
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> Result<int, IoError> {
+        let mut total = 0i;
+        for buf in bufs.mut_iter() {
+            match self.read(*buf) {
+                Ok(n) => { total += n; if n < buf.len() as int { break } }
+                Err(e) => return if total > 0 { Ok(total) } else { Err(e) },
+            }
+        }
+        Ok(total)
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> Result<(), IoError> {
+        for buf in bufs.iter() {
+            try!(self.write(*buf));
+        }
+        Ok(())
+    }
+
+    // FIXME: backed by flock(2)/LockFileEx in the native and libuv
+    // implementors; no such backend lives in this file so these default to
+    // rejecting with IoUnavailable rather than leaving implementors broken.
+    // FIXME: `try_lock`/`try_lock_shared` want a dedicated "lock already
+    // held" IoErrorKind (e.g. ResourceUnavailable) once one exists in
+    // io::IoErrorKind; that enum isn't part of this file, so they fall back
+    // to IoUnavailable like the rest of these defaults for now.
+    fn lock(&mut self) -> Result<(), IoError> {
+        Err(io::standard_error(io::IoUnavailable))
+    }
+    fn try_lock(&mut self) -> Result<(), IoError> {
+        Err(io::standard_error(io::IoUnavailable))
+    }
+    fn lock_shared(&mut self) -> Result<(), IoError> {
+        Err(io::standard_error(io::IoUnavailable))
+    }
+    fn try_lock_shared(&mut self) -> Result<(), IoError> {
+        Err(io::standard_error(io::IoUnavailable))
+    }
+    fn unlock(&mut self) -> Result<(), IoError> {
+        Err(io::standard_error(io::IoUnavailable))
+    }
 }
 
 pub trait RtioProcess {
@@ -258,6 +412,42 @@ pub trait RtioPipe {
     fn read(&mut self, buf: &mut [u8]) -> Result<uint, IoError>;
     fn write(&mut self, buf: &[u8]) -> Result<(), IoError>;
     fn clone(&self) -> ~RtioPipe;
+
+    fn readv(&mut self, bufs: &mut [&mut [u8]]) -> Result<uint, IoError> {
+        let mut total = 0u;
+        for buf in bufs.mut_iter() {
+            match self.read(*buf) {
+                Ok(n) => { total += n; if n < buf.len() { break } }
+                Err(e) => return if total > 0 { Ok(total) } else { Err(e) },
+            }
+        }
+        Ok(total)
+    }
+    fn writev(&mut self, bufs: &[&[u8]]) -> Result<(), IoError> {
+        for buf in bufs.iter() {
+            try!(self.write(*buf));
+        }
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_read_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_write_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
 }
 
 pub trait RtioUnixListener {
@@ -274,6 +464,25 @@ pub trait RtioTTY {
     fn set_raw(&mut self, raw: bool) -> Result<(), IoError>;
     fn get_winsize(&mut self) -> Result<(int, int), IoError>;
     fn isatty(&self) -> bool;
+
+    fn set_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_read_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
+    fn set_write_timeout(&mut self, timeout_ms: Option<u64>) -> Result<(), IoError> {
+        match timeout_ms {
+            None => Ok(()),
+            Some(_) => Err(io::standard_error(io::IoUnavailable)),
+        }
+    }
 }
 
 pub trait PausableIdleCallback {
@@ -282,3 +491,9 @@ pub trait PausableIdleCallback {
 }
 
 pub trait RtioSignal {}
+
+/// A handle to an active filesystem watch, created via
+/// `IoFactory::fs_watch`. Dropping the handle stops the watch and, where
+/// the backing implementation requires it, tears down the underlying
+/// inotify/kqueue/`ReadDirectoryChangesW` watch.
+pub trait RtioFsWatcher {}